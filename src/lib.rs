@@ -3,21 +3,32 @@
 //  Licensed under MIT License, see License file for more details
 //  git clone https://github.com/marcomq/fast-uuid-v7
 
+#![cfg_attr(feature = "no_std", no_std)]
+
+#[cfg(not(feature = "no_std"))]
+use core::cell::RefCell;
+#[cfg(not(feature = "no_std"))]
+use core::sync::atomic::{AtomicU64, Ordering};
 use rand::rngs::SmallRng;
 use rand::{RngCore, SeedableRng};
-use std::cell::RefCell;
+#[cfg(not(feature = "no_std"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(not(feature = "no_std"))]
 struct ThreadState {
     rng: SmallRng,
     last_ms: u64,
-    counter: u32,
+    // u128 so it can back the full 74-bit counter gen_id_with_count_bits allows
+    // (12 bits of rand_a + all 62 usable bits of rand_b); a u32/u64 would silently
+    // saturate well short of that and waste the upper bits of wide counters.
+    counter: u128,
     #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
     last_tsc: u64,
     #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
     threshold: u64,
 }
 
+#[cfg(not(feature = "no_std"))]
 impl ThreadState {
     fn new() -> Self {
         let rng = SmallRng::from_rng(&mut rand::rng());
@@ -122,8 +133,11 @@ impl ThreadState {
         self.last_ms
     }
 
+    /// Gets the current timestamp and the next counter value for a `width`-bit
+    /// counter, rolling the counter over (and bumping the timestamp to preserve
+    /// monotonicity) once it reaches `(1 << width) - 1`.
     #[inline(always)]
-    fn get_time_and_counter(&mut self) -> (u64, u32) {
+    fn get_time_and_counter_bits(&mut self, width: u32) -> (u64, u128) {
         let should_check = self.should_check_time();
 
         let mut current_timestamp = if should_check {
@@ -132,6 +146,11 @@ impl ThreadState {
             self.last_ms
         };
 
+        // Max counter value for the configured width. `width` is always <=
+        // COUNTER_BITS_MAX (74), well under u128's 128 bits, so no need to
+        // guard against an overflowing shift the way a u32/u64 counter would.
+        let max_counter = (1u128 << width) - 1;
+
         if current_timestamp > self.last_ms {
             self.last_ms = current_timestamp;
             self.counter = 0;
@@ -141,8 +160,8 @@ impl ThreadState {
             current_timestamp = self.last_ms;
             let c = self.counter;
 
-            // If counter is exhausted (18 bits = 262,143), increment timestamp to preserve monotonicity
-            if c >= 0x3FFFF {
+            // If counter is exhausted, increment timestamp to preserve monotonicity.
+            if c >= max_counter {
                 current_timestamp += 1;
                 self.last_ms = current_timestamp;
                 self.counter = 0;
@@ -156,6 +175,17 @@ impl ThreadState {
     }
 }
 
+/// Default counter width (in bits) used by [`gen_id_with_count`]: 12 bits in rand_a,
+/// 6 bits in the high part of rand_b, leaving 56 bits of randomness.
+#[cfg(not(feature = "no_std"))]
+const COUNTER_BITS_DEFAULT: u32 = 18;
+
+/// Largest counter width supported by [`gen_id_with_count_bits`]: 12 bits of rand_a
+/// plus all 62 usable bits of rand_b, leaving no randomness at all.
+#[cfg(not(feature = "no_std"))]
+const COUNTER_BITS_MAX: u32 = 74;
+
+#[cfg(not(feature = "no_std"))]
 thread_local! {
     static STATE: RefCell<ThreadState> = RefCell::new(ThreadState::new());
 }
@@ -175,6 +205,10 @@ thread_local! {
 /// for IDs generated within the same millisecond on the same thread.
 ///
 /// fast-uuid-v7 is is not random enough for cryptography!
+///
+/// Not available under the `no_std` feature; use [`Generator`] with an
+/// injected [`MillisClock`] instead.
+#[cfg(not(feature = "no_std"))]
 #[inline]
 pub fn gen_id_u128() -> u128 {
     STATE.with(|state_cell| {
@@ -199,6 +233,7 @@ pub fn gen_id_u128() -> u128 {
 }
 
 /// Alias for `gen_id_u128`.
+#[cfg(not(feature = "no_std"))]
 #[inline]
 pub fn gen_id() -> u128 {
     gen_id_u128()
@@ -214,11 +249,13 @@ pub fn gen_id() -> u128 {
 /// to be globally monotonic.
 ///
 /// This is not random enough for cryptography!
+#[cfg(not(feature = "no_std"))]
 pub fn gen_id_string() -> String {
     gen_id_str().to_string()
 }
 
 /// Generates a UUID v7 string on the stack, avoiding heap allocation.
+#[cfg(not(feature = "no_std"))]
 pub fn gen_id_str() -> UuidString {
     format_uuid(gen_id_u128())
 }
@@ -282,6 +319,64 @@ pub fn format_uuid(id: u128) -> UuidString {
     out
 }
 
+/// Extracts the 48-bit Unix-millisecond timestamp embedded in a generated ID.
+///
+/// Works on IDs produced by any function in this crate (`gen_id_u128`,
+/// `gen_id_with_count`, `gen_id_with_count_bits`, ...), since they all place
+/// the timestamp in the same top 48 bits.
+#[inline]
+pub fn timestamp_ms(id: u128) -> u64 {
+    (id >> 80) as u64
+}
+
+/// Reassembles the 18-bit monotonic counter from an ID produced by
+/// `gen_id_with_count` (or `gen_id_with_count_bits::<18>()`).
+///
+/// The counter is split exactly as `gen_id_with_count` lays it out: the top
+/// 12 bits live in rand_a, the low 6 bits in the high bits of rand_b. Calling
+/// this on an ID from `gen_id_u128` (no counter) or from
+/// `gen_id_with_count_bits` with a different width returns meaningless bits.
+#[inline]
+pub fn counter(id: u128) -> u32 {
+    let rand_a = ((id >> 64) & 0xFFF) as u32;
+    let rand_b_high = ((id >> 56) & 0x3F) as u32;
+    (rand_a << 6) | rand_b_high
+}
+
+/// Parses a hyphenated UUID string (`xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`)
+/// back into a `u128`, inverting `format_uuid`.
+///
+/// Returns `None` if the string isn't exactly 36 bytes, has dashes in the
+/// wrong positions, or contains non-hex characters elsewhere.
+pub fn parse_uuid(s: &str) -> Option<u128> {
+    const DASH_POSITIONS: [usize; 4] = [8, 13, 18, 23];
+
+    let bytes = s.as_bytes();
+    if bytes.len() != 36 {
+        return None;
+    }
+    for &i in &DASH_POSITIONS {
+        if bytes[i] != b'-' {
+            return None;
+        }
+    }
+
+    let mut out: u128 = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        if DASH_POSITIONS.contains(&i) {
+            continue;
+        }
+        let nibble = match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'f' => b - b'a' + 10,
+            b'A'..=b'F' => b - b'A' + 10,
+            _ => return None,
+        };
+        out = (out << 4) | nibble as u128;
+    }
+    Some(out)
+}
+
 /// A stack-allocated string representation of a UUID (36 bytes).
 ///
 /// This type implements `Deref<Target=str>`, so it can be used like a `&str`.
@@ -289,11 +384,11 @@ pub fn format_uuid(id: u128) -> UuidString {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct UuidString([u8; 36]);
 
-impl std::ops::Deref for UuidString {
+impl core::ops::Deref for UuidString {
     type Target = str;
     fn deref(&self) -> &str {
         // SAFETY: The buffer is always filled with valid ASCII (hex + dashes)
-        unsafe { std::str::from_utf8_unchecked(&self.0) }
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
     }
 }
 
@@ -315,8 +410,8 @@ impl PartialEq<&str> for UuidString {
     }
 }
 
-impl std::fmt::Display for UuidString {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for UuidString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(self)
     }
 }
@@ -324,6 +419,7 @@ impl std::fmt::Display for UuidString {
 /// Returns the current time in milliseconds since the Unix epoch.
 ///
 /// It returns `0` if the system clock hasn't started yet.
+#[cfg(not(feature = "no_std"))]
 #[inline]
 fn system_time_ms() -> u64 {
     SystemTime::now()
@@ -336,24 +432,227 @@ fn system_time_ms() -> u64 {
 ///
 /// This guarantees per-thread monotonicity (up to ~262k IDs/ms) but has higher
 /// collision risk across different nodes if the random part is exhausted.
+///
+/// Equivalent to `gen_id_with_count_bits::<18>()`. Use [`gen_id_with_count_bits`]
+/// directly to trade counter width for randomness.
+#[cfg(not(feature = "no_std"))]
 #[inline]
 pub fn gen_id_with_count() -> u128 {
+    gen_id_with_count_bits::<COUNTER_BITS_DEFAULT>()
+}
+
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_with_count_str() -> UuidString {
+    format_uuid(gen_id_with_count())
+}
+
+/// Generates a UUID v7 with a `W`-bit monotonic counter.
+///
+/// The counter occupies `min(W, 12)` bits of rand_a and the remaining `W - 12`
+/// bits in the high part of rand_b, exactly as `gen_id_with_count` lays out its
+/// fixed 18-bit counter; whatever is left of rand_b's 62 bits stays random.
+/// Wider `W` gives stronger per-thread sort/monotonicity guarantees (more IDs/ms
+/// before the timestamp has to be bumped) at the cost of entropy, which matters
+/// most when IDs from independent nodes can collide. `W` must not exceed 74
+/// (12 bits of rand_a plus all 62 usable bits of rand_b).
+///
+/// # Panics
+///
+/// Panics if `W > 74`.
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_with_count_bits<const W: u32>() -> u128 {
+    assert!(
+        W <= COUNTER_BITS_MAX,
+        "counter width must not exceed {COUNTER_BITS_MAX} bits"
+    );
+
     STATE.with(|state_cell| {
         let mut state = state_cell.borrow_mut();
-        let (timestamp, counter) = state.get_time_and_counter();
+        let (timestamp, counter) = state.get_time_and_counter_bits(W);
 
         let timestamp_part = (timestamp as u128) << 80;
         let version_part = 7u128 << 76; // Version 7 (0111)
         let variant_part = 2u128 << 62; // Variant 1 (10..), RFC 4122
 
-        // Use 18 bits for counter: 12 in rand_a, 6 in rand_b high.
+        // `bits_in_a` bits of the counter live in rand_a, the rest in rand_b's high bits.
+        let bits_in_a = W.min(12);
+        let bits_in_b = W - bits_in_a;
+
+        let mask_a: u128 = if bits_in_a == 0 { 0 } else { (1u128 << bits_in_a) - 1 };
+        let counter_a_bits: u128 = (counter >> bits_in_b) & mask_a;
+
+        // `bits_in_b` is at most 62, so this always fits in a u64.
+        let mask_b: u128 = if bits_in_b == 0 { 0 } else { (1u128 << bits_in_b) - 1 };
+        let counter_b_bits = (counter & mask_b) as u64;
+
+        let random_bits_in_b = 62 - bits_in_b;
+        let rand_nr = state.rng.next_u64();
+        let random_mask = (1u64 << random_bits_in_b) - 1;
+
+        // Whatever of rand_a's 12 bits the counter doesn't use stays random,
+        // same as the unused portion of rand_b above. The counter bits must
+        // stay the most significant bits of the field (as rand_b's do) so the
+        // random filler can never outweigh the counter and break ordering.
+        let random_bits_in_a = 12 - bits_in_a;
+        let rand_a_random_mask = (1u64 << random_bits_in_a) - 1;
+        let rand_a_nr = state.rng.next_u64() & rand_a_random_mask;
+        let rand_a = (counter_a_bits << random_bits_in_a) | (rand_a_nr as u128);
+
+        let counter_part = rand_a << 64;
+        let random_part = ((counter_b_bits << random_bits_in_b) | (rand_nr & random_mask)) as u128;
+
+        timestamp_part | version_part | counter_part | variant_part | random_part
+    })
+}
+
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_with_count_bits_str<const W: u32>() -> UuidString {
+    format_uuid(gen_id_with_count_bits::<W>())
+}
+
+/// Packed `(last_ms << 18) | counter` state shared by [`gen_id_global`] across
+/// every thread in the process. 46 bits for the millisecond timestamp leaves
+/// room for the 18-bit counter in a single `AtomicU64`, and is good for
+/// roughly 2000 years past the Unix epoch - less than the 48-bit timestamp
+/// field this crate otherwise uses, but far beyond any practical date.
+#[cfg(not(feature = "no_std"))]
+static GLOBAL_STATE: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(not(feature = "no_std"))]
+const GLOBAL_COUNTER_MASK: u64 = (1 << COUNTER_BITS_DEFAULT) - 1;
+
+/// CAS loop advancing `GLOBAL_STATE`, mirroring the per-thread
+/// `get_time_and_counter_bits` rollover logic: if the counter is exhausted
+/// for the current millisecond, bump the millisecond instead of wrapping.
+#[cfg(not(feature = "no_std"))]
+#[inline]
+fn global_next_time_and_counter() -> (u64, u32) {
+    loop {
+        let now = system_time_ms();
+        let prev = GLOBAL_STATE.load(Ordering::Relaxed);
+        let prev_ms = prev >> COUNTER_BITS_DEFAULT;
+        let prev_counter = prev & GLOBAL_COUNTER_MASK;
+
+        let (new_ms, new_counter) = if now > prev_ms {
+            (now, 0)
+        } else if prev_counter >= GLOBAL_COUNTER_MASK {
+            (prev_ms + 1, 0)
+        } else {
+            (prev_ms, prev_counter + 1)
+        };
+
+        let new_packed = (new_ms << COUNTER_BITS_DEFAULT) | new_counter;
+        if GLOBAL_STATE
+            .compare_exchange_weak(prev, new_packed, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            return (new_ms, new_counter as u32);
+        }
+        // Another thread won the race; reload and retry with a fresh `now`.
+    }
+}
+
+/// Generates a UUID v7 with an 18-bit counter that is strictly increasing
+/// across *every* thread in the process, not just the calling thread.
+///
+/// Unlike `gen_id_with_count`, which only guarantees per-thread monotonicity,
+/// this updates a single shared `AtomicU64` via a CAS loop, so IDs handed to
+/// a single sorted structure (an index, a log) from multiple threads come
+/// out in the same strictly-increasing order they were generated. The
+/// tradeoff is the same CAS contention any shared-counter design pays under
+/// highly concurrent generation.
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_global() -> u128 {
+    let (timestamp, counter) = global_next_time_and_counter();
+
+    let timestamp_part = (timestamp as u128) << 80;
+    let version_part = 7u128 << 76; // Version 7 (0111)
+    let variant_part = 2u128 << 62; // Variant 1 (10..), RFC 4122
+
+    // Same 18-bit layout as gen_id_with_count: 12 bits in rand_a, 6 in rand_b high.
+    let rand_a = (counter >> 6) & 0xFFF;
+    let rand_b_high = counter & 0x3F;
+
+    let rand_nr = STATE.with(|state_cell| state_cell.borrow_mut().rng.next_u64());
+
+    let counter_part = (rand_a as u128) << 64;
+    let rand_b_low = rand_nr & 0x00FF_FFFF_FFFF_FFFF;
+    let random_part = ((rand_b_high as u128) << 56) | (rand_b_low as u128);
+
+    timestamp_part | version_part | counter_part | variant_part | random_part
+}
+
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_global_str() -> UuidString {
+    format_uuid(gen_id_global())
+}
+
+/// Generates a UUID v7 with the timestamp field set to the caller-supplied
+/// `ms` (milliseconds since Unix epoch) instead of the current time.
+///
+/// Useful for backfilling historical records, deterministic tests, and
+/// reproducing IDs for fixtures. The random bits still come from the
+/// thread-local RNG `gen_id_u128` uses, but this never reads or mutates the
+/// `last_ms`/`counter` state the wall-clock functions rely on, so it doesn't
+/// disturb their ongoing monotonicity.
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_at_ms(ms: u64) -> u128 {
+    STATE.with(|state_cell| {
+        let mut state = state_cell.borrow_mut();
+
+        let timestamp_part = (ms as u128) << 80;
+        let version_part = 7u128 << 76; // Version 7 (0111)
+        let variant_part = 2u128 << 62; // Variant 1 (10..), RFC 4122
+
+        let r1 = state.rng.next_u32();
+        let r2 = state.rng.next_u64();
+
+        let rand_a = (r1 & 0xFFF) as u128;
+        let rand_b = (r2 & 0x3FFFFFFFFFFFFFFF) as u128;
+
+        timestamp_part | version_part | (rand_a << 64) | variant_part | rand_b
+    })
+}
+
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_at_ms_str(ms: u64) -> UuidString {
+    format_uuid(gen_id_at_ms(ms))
+}
+
+/// Same as `gen_id_at_ms`, but lays out its 18-bit counter slot the same way
+/// `gen_id_with_count` does.
+///
+/// There is no live counter to continue at an arbitrary caller-supplied
+/// timestamp, so the counter slot is drawn fresh from the thread-local RNG
+/// rather than incremented - exactly like the rest of the random bits. This
+/// function does not touch the ongoing `last_ms`/`counter` monotonic state
+/// either, so IDs it returns carry no ordering guarantee relative to each
+/// other or to `gen_id_with_count`'s output; use it for fixtures and
+/// backfills, not for generating a live monotonic sequence.
+#[cfg(not(feature = "no_std"))]
+#[inline]
+pub fn gen_id_at_ms_with_count(ms: u64) -> u128 {
+    STATE.with(|state_cell| {
+        let mut state = state_cell.borrow_mut();
+
+        let timestamp_part = (ms as u128) << 80;
+        let version_part = 7u128 << 76; // Version 7 (0111)
+        let variant_part = 2u128 << 62; // Variant 1 (10..), RFC 4122
+
+        let counter = state.rng.next_u32() & ((1u32 << COUNTER_BITS_DEFAULT) - 1);
         let rand_a = (counter >> 6) & 0xFFF;
         let rand_b_high = counter & 0x3F;
 
         let rand_nr = state.rng.next_u64();
 
-        let counter_part = (rand_a as u128) << 64; // 12 bits of counter
-                                                   // 56 bits of randomness + 6 bits of counter
+        let counter_part = (rand_a as u128) << 64;
         let rand_b_low = rand_nr & 0x00FF_FFFF_FFFF_FFFF;
         let random_part = ((rand_b_high as u128) << 56) | (rand_b_low as u128);
 
@@ -361,12 +660,138 @@ pub fn gen_id_with_count() -> u128 {
     })
 }
 
+#[cfg(not(feature = "no_std"))]
 #[inline]
-pub fn gen_id_with_count_str() -> UuidString {
-    format_uuid(gen_id_with_count())
+pub fn gen_id_at_ms_with_count_str(ms: u64) -> UuidString {
+    format_uuid(gen_id_at_ms_with_count(ms))
 }
 
-#[cfg(test)]
+/// Injectable source of the current Unix-millisecond time.
+///
+/// Implement this to drive [`Generator`] from whatever clock the host
+/// platform provides (a WASM `Date.now()` import, an embedded RTC, a fake
+/// clock in tests, ...) instead of `std::time::SystemTime`.
+pub trait MillisClock {
+    /// Returns the current Unix timestamp in milliseconds.
+    fn now_ms(&self) -> u64;
+}
+
+/// The default [`MillisClock`] for host platforms with `std`, backed by
+/// `SystemTime::now()`.
+#[cfg(not(feature = "no_std"))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdClock;
+
+#[cfg(not(feature = "no_std"))]
+impl MillisClock for StdClock {
+    #[inline]
+    fn now_ms(&self) -> u64 {
+        system_time_ms()
+    }
+}
+
+/// A UUID v7 generator driven by an explicit [`MillisClock`] instead of the
+/// thread-local, `SystemTime`-backed state used by the free functions
+/// (`gen_id_u128`, `gen_id_with_count`, ...).
+///
+/// Unlike the free functions, `Generator` carries no implicit thread-local
+/// state: create one per thread, task, or however the host schedules work,
+/// and call its methods directly. This is the path to use under the
+/// `no_std` feature, and on targets (WASM, embedded) where a cheap
+/// thread-local `SystemTime` isn't available.
+///
+/// Seeding uses `SmallRng::seed_from_u64` rather than OS randomness, since
+/// an OS entropy source isn't guaranteed to exist under `no_std`.
+pub struct Generator<C: MillisClock> {
+    clock: C,
+    rng: SmallRng,
+    last_ms: u64,
+    counter: u32,
+}
+
+impl<C: MillisClock> Generator<C> {
+    /// Creates a new generator using `clock` for timestamps and `seed` to
+    /// seed its PRNG.
+    pub fn new(clock: C, seed: u64) -> Self {
+        Self {
+            clock,
+            rng: SmallRng::seed_from_u64(seed),
+            last_ms: 0,
+            counter: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn get_time_and_counter(&mut self) -> (u64, u32) {
+        let now = self.clock.now_ms();
+
+        if now > self.last_ms {
+            self.last_ms = now;
+            self.counter = 0;
+            (now, 0)
+        } else {
+            let c = self.counter;
+            // If counter is exhausted (18 bits = 262,143), increment timestamp to preserve monotonicity.
+            if c >= 0x3FFFF {
+                self.last_ms += 1;
+                self.counter = 0;
+                (self.last_ms, 0)
+            } else {
+                self.counter = c.wrapping_add(1);
+                (self.last_ms, self.counter)
+            }
+        }
+    }
+
+    /// Generates a UUID v7 value using 74 bits of randomness from this
+    /// generator's clock and PRNG. See `gen_id_u128` for the layout.
+    #[inline]
+    pub fn gen_id_u128(&mut self) -> u128 {
+        let timestamp = self.clock.now_ms();
+
+        let timestamp_part = (timestamp as u128) << 80;
+        let version_part = 7u128 << 76;
+        let variant_part = 2u128 << 62;
+
+        let r1 = self.rng.next_u32();
+        let r2 = self.rng.next_u64();
+
+        let rand_a = (r1 & 0xFFF) as u128;
+        let rand_b = (r2 & 0x3FFFFFFFFFFFFFFF) as u128;
+
+        timestamp_part | version_part | (rand_a << 64) | variant_part | rand_b
+    }
+
+    /// Generates a UUID v7 value with an 18-bit monotonic counter, the same
+    /// layout as the free function `gen_id_with_count`.
+    #[inline]
+    pub fn gen_id_with_count(&mut self) -> u128 {
+        let (timestamp, counter) = self.get_time_and_counter();
+
+        let timestamp_part = (timestamp as u128) << 80;
+        let version_part = 7u128 << 76;
+        let variant_part = 2u128 << 62;
+
+        let rand_a = (counter >> 6) & 0xFFF;
+        let rand_b_high = counter & 0x3F;
+
+        let rand_nr = self.rng.next_u64();
+
+        let counter_part = (rand_a as u128) << 64;
+        let rand_b_low = rand_nr & 0x00FF_FFFF_FFFF_FFFF;
+        let random_part = ((rand_b_high as u128) << 56) | (rand_b_low as u128);
+
+        timestamp_part | version_part | counter_part | variant_part | random_part
+    }
+
+    /// Generates a UUID v7 string on the stack, avoiding heap allocation.
+    #[inline]
+    pub fn gen_id_str(&mut self) -> UuidString {
+        format_uuid(self.gen_id_u128())
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
 mod tests {
     use super::*;
 
@@ -423,6 +848,56 @@ mod tests {
         assert_eq!(formatted.as_ref(), uuid_crate_str);
     }
 
+    #[test]
+    fn test_parse_uuid_roundtrip() {
+        for _ in 0..10_000 {
+            let id = gen_id_u128();
+            let s = format_uuid(id);
+            assert_eq!(parse_uuid(&s), Some(id));
+        }
+    }
+
+    #[test]
+    fn test_parse_uuid_rejects_malformed() {
+        assert_eq!(parse_uuid(""), None);
+        assert_eq!(parse_uuid("not-a-uuid"), None);
+        // wrong dash positions (36 chars, but the first dash is one slot early)
+        assert_eq!(
+            parse_uuid("0123456-789ab-cdef-0123-456789abcdef"),
+            None
+        );
+        // non-hex character
+        assert_eq!(
+            parse_uuid("0123456g-89ab-cdef-0123-456789abcdef"),
+            None
+        );
+        // one char short
+        assert_eq!(
+            parse_uuid("0123456-89ab-cdef-0123-456789abcdef"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_timestamp_ms_roundtrip() {
+        let before = system_time_ms();
+        let id = gen_id_u128();
+        let after = system_time_ms();
+        let ts = timestamp_ms(id);
+        assert!(ts >= before && ts <= after);
+    }
+
+    #[test]
+    fn test_counter_roundtrip() {
+        for expected in [0u32, 1, 42, 0x3FFFF] {
+            // Build a synthetic ID the way gen_id_with_count lays out its 18-bit counter.
+            let rand_a = (expected >> 6) & 0xFFF;
+            let rand_b_high = expected & 0x3F;
+            let id = ((rand_a as u128) << 64) | ((rand_b_high as u128) << 56);
+            assert_eq!(counter(id), expected);
+        }
+    }
+
     #[test]
     fn test_gen_id_structure() {
         let id = gen_id();
@@ -508,4 +983,353 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_gen_id_with_count_bits_structure() {
+        let id = gen_id_with_count_bits::<24>();
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_gen_id_with_count_bits_matches_default_width() {
+        // `gen_id_with_count` must be exactly `gen_id_with_count_bits::<18>()`.
+        for _ in 0..1_000 {
+            let a = gen_id_with_count();
+            let b = gen_id_with_count_bits::<18>();
+            // Both share the same version/variant layout and the same
+            // thread-local counter, so `b` carries the counter value right
+            // after `a`'s (or 0 once the timestamp has moved on).
+            assert_eq!((a >> 76) & 0xF, (b >> 76) & 0xF, "version mismatch");
+            assert_eq!((a >> 62) & 0x3, (b >> 62) & 0x3, "variant mismatch");
+            if timestamp_ms(b) == timestamp_ms(a) {
+                assert_eq!(counter(b), counter(a) + 1, "counter layout mismatch");
+            } else {
+                assert!(timestamp_ms(b) > timestamp_ms(a));
+                assert_eq!(counter(b), 0, "counter layout mismatch");
+            }
+        }
+    }
+
+    #[test]
+    fn test_gen_id_with_count_bits_ordering_narrow_width() {
+        // A narrow counter (8 bits) still must not break per-thread monotonicity.
+        let mut last_id = 0;
+        for _ in 0..1_000_000 {
+            let id = gen_id_with_count_bits::<8>();
+            if last_id != 0 {
+                assert!(
+                    id > last_id,
+                    "IDs are not ordered: {:032x} <= {:032x}",
+                    id,
+                    last_id
+                );
+            }
+            last_id = id;
+        }
+    }
+
+    #[test]
+    fn test_gen_id_with_count_bits_narrow_width_fills_unused_rand_a_bits() {
+        // With an 8-bit counter, only the top 8 of rand_a's 12 bits carry the
+        // counter; the low 4 must be filled with randomness, not left at zero.
+        let mut seen_nonzero = false;
+        for _ in 0..1_000 {
+            let id = gen_id_with_count_bits::<8>();
+            let unused_rand_a_bits = (id >> 64) & 0xF;
+            if unused_rand_a_bits != 0 {
+                seen_nonzero = true;
+                break;
+            }
+        }
+        assert!(
+            seen_nonzero,
+            "low 4 bits of rand_a were zero across 1000 samples; entropy is not being filled"
+        );
+    }
+
+    #[test]
+    fn test_gen_id_with_count_bits_ordering_wide_width() {
+        // A counter wider than the default 18 bits (spilling further into rand_b)
+        // must still preserve per-thread ordering.
+        let mut last_id = 0;
+        for _ in 0..1_000_000 {
+            let id = gen_id_with_count_bits::<30>();
+            if last_id != 0 {
+                assert!(
+                    id > last_id,
+                    "IDs are not ordered: {:032x} <= {:032x}",
+                    id,
+                    last_id
+                );
+            }
+            last_id = id;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "counter width must not exceed")]
+    fn test_gen_id_with_count_bits_rejects_too_wide() {
+        let _ = gen_id_with_count_bits::<75>();
+    }
+
+    #[test]
+    fn test_get_time_and_counter_bits_rolls_over_above_32_bits() {
+        // Regression test: a u32-backed counter pinned its rollover threshold
+        // at u32::MAX for any width >= 32, silently truncating wider counters.
+        const WIDTH: u32 = 40;
+
+        STATE.with(|cell| {
+            let mut state = cell.borrow_mut();
+            // Pin last_ms far in the future so the real clock never looks
+            // newer and the "time hasn't moved forward" branch is exercised.
+            state.last_ms = 1_000_000_000_000_000;
+            // One below the true 40-bit threshold; under the old u32::MAX-pinned
+            // logic this value (> u32::MAX) was already considered exhausted.
+            state.counter = (1u128 << WIDTH) - 2;
+        });
+
+        let (ts1, counter1) =
+            STATE.with(|cell| cell.borrow_mut().get_time_and_counter_bits(WIDTH));
+        assert_eq!(
+            counter1,
+            (1u128 << WIDTH) - 1,
+            "counter should have incremented by one, not rolled over early"
+        );
+        assert_eq!(ts1, 1_000_000_000_000_000, "timestamp should not bump early");
+
+        // The counter is now genuinely exhausted for this width; the next call
+        // must roll over to 0 and bump the timestamp.
+        let (ts2, counter2) =
+            STATE.with(|cell| cell.borrow_mut().get_time_and_counter_bits(WIDTH));
+        assert_eq!(counter2, 0);
+        assert_eq!(ts2, 1_000_000_000_000_001);
+    }
+
+    /// A clock that advances by a fixed step on every call, for deterministic tests.
+    struct FakeClock {
+        step_ms: u64,
+        now: RefCell<u64>,
+    }
+
+    impl MillisClock for FakeClock {
+        fn now_ms(&self) -> u64 {
+            let mut now = self.now.borrow_mut();
+            *now += self.step_ms;
+            *now
+        }
+    }
+
+    #[test]
+    fn test_generator_gen_id_u128_structure() {
+        let mut gen = Generator::new(FakeClock { step_ms: 1, now: RefCell::new(0) }, 42);
+        let id = gen.gen_id_u128();
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_generator_gen_id_with_count_ordering() {
+        // Clock never advances, so every ID shares millisecond 1 and only the counter moves.
+        let mut gen = Generator::new(FakeClock { step_ms: 0, now: RefCell::new(1) }, 7);
+        let mut last_id = 0;
+        for _ in 0..100_000 {
+            let id = gen.gen_id_with_count();
+            if last_id != 0 {
+                assert!(
+                    id > last_id,
+                    "IDs are not ordered: {:032x} <= {:032x}",
+                    id,
+                    last_id
+                );
+            }
+            last_id = id;
+        }
+    }
+
+    #[test]
+    fn test_generator_uses_injected_timestamp() {
+        let mut gen = Generator::new(FakeClock { step_ms: 5, now: RefCell::new(1_000) }, 1);
+        let id = gen.gen_id_u128();
+        assert_eq!(timestamp_ms(id), 1_005);
+    }
+
+    #[test]
+    fn test_generator_gen_id_str_roundtrip() {
+        let mut gen = Generator::new(FakeClock { step_ms: 1, now: RefCell::new(0) }, 99);
+        let s = gen.gen_id_str();
+        assert!(parse_uuid(&s).is_some());
+    }
+
+    #[test]
+    fn test_gen_id_global_structure() {
+        let id = gen_id_global();
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_gen_id_global_ordering_single_thread() {
+        let mut last_id = 0;
+        for _ in 0..100_000 {
+            let id = gen_id_global();
+            assert!(
+                id > last_id,
+                "IDs are not ordered: {:032x} <= {:032x}",
+                id,
+                last_id
+            );
+            last_id = id;
+        }
+    }
+
+    #[test]
+    fn test_gen_id_global_ordering_across_threads() {
+        use std::sync::{Arc, Barrier};
+
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 20_000;
+
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    let mut ids = Vec::with_capacity(PER_THREAD);
+                    for _ in 0..PER_THREAD {
+                        let id = gen_id_global();
+                        // Other threads advance the shared state in between,
+                        // but each thread's own IDs must still strictly increase.
+                        if let Some(&last) = ids.last() {
+                            assert!(id > last, "IDs are not ordered: {:032x} <= {:032x}", id, last);
+                        }
+                        ids.push(id);
+                    }
+                    ids
+                })
+            })
+            .collect();
+
+        let mut all_ids: Vec<u128> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        assert_eq!(all_ids.len(), THREADS * PER_THREAD);
+
+        all_ids.sort_unstable();
+        let mut unique = all_ids.clone();
+        unique.dedup();
+        assert_eq!(
+            unique.len(),
+            all_ids.len(),
+            "gen_id_global produced duplicate IDs across threads"
+        );
+    }
+
+    #[test]
+    fn test_gen_id_at_ms_uses_given_timestamp() {
+        let id = gen_id_at_ms(1_700_000_000_000);
+        assert_eq!(timestamp_ms(id), 1_700_000_000_000);
+
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_gen_id_at_ms_with_count_uses_given_timestamp() {
+        let id = gen_id_at_ms_with_count(42);
+        assert_eq!(timestamp_ms(id), 42);
+
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_gen_id_at_ms_does_not_disturb_live_clock_state() {
+        // Drive the real wall-clock counter forward first...
+        let before = gen_id_with_count();
+
+        // ...then generate a batch of backfilled/fixture IDs at an arbitrary,
+        // unrelated timestamp...
+        for ms in 0..1_000 {
+            let _ = gen_id_at_ms_with_count(ms);
+        }
+
+        // ...and confirm the live monotonic sequence picks up right where it
+        // left off instead of restarting at `ms`'s timestamp/counter.
+        let after = gen_id_with_count();
+        assert!(
+            after > before,
+            "live counter state was disturbed by gen_id_at_ms_with_count: {:032x} <= {:032x}",
+            after,
+            before
+        );
+        assert!(timestamp_ms(after) >= timestamp_ms(before));
+    }
+}
+
+/// Tests for the subset of the API that's available under the `no_std`
+/// feature: `MillisClock`, `Generator`, and the `format_uuid`/`parse_uuid`
+/// decode helpers. The `mod tests` above exercises the thread-local, `std`-only
+/// API and can't be compiled under `no_std`.
+#[cfg(all(test, feature = "no_std"))]
+mod no_std_tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    struct FakeClock {
+        step_ms: u64,
+        now: RefCell<u64>,
+    }
+
+    impl MillisClock for FakeClock {
+        fn now_ms(&self) -> u64 {
+            let mut now = self.now.borrow_mut();
+            *now += self.step_ms;
+            *now
+        }
+    }
+
+    #[test]
+    fn test_generator_gen_id_u128_structure() {
+        let mut gen = Generator::new(FakeClock { step_ms: 1, now: RefCell::new(0) }, 42);
+        let id = gen.gen_id_u128();
+        let uuid = uuid::Uuid::from_u128(id);
+        assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_generator_gen_id_with_count_ordering() {
+        let mut gen = Generator::new(FakeClock { step_ms: 0, now: RefCell::new(1) }, 7);
+        let mut last_id = 0;
+        for _ in 0..1_000 {
+            let id = gen.gen_id_with_count();
+            if last_id != 0 {
+                assert!(id > last_id, "IDs are not ordered");
+            }
+            last_id = id;
+        }
+    }
+
+    #[test]
+    fn test_generator_uses_injected_timestamp() {
+        let mut gen = Generator::new(FakeClock { step_ms: 5, now: RefCell::new(1_000) }, 1);
+        let id = gen.gen_id_u128();
+        assert_eq!(timestamp_ms(id), 1_005);
+    }
+
+    #[test]
+    fn test_format_and_parse_uuid_roundtrip() {
+        let mut gen = Generator::new(FakeClock { step_ms: 1, now: RefCell::new(0) }, 99);
+        let id = gen.gen_id_u128();
+        let s = format_uuid(id);
+        assert_eq!(parse_uuid(&s), Some(id));
+    }
 }